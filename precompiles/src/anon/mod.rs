@@ -0,0 +1,5 @@
+pub mod deposit;
+pub mod transfer;
+
+pub use deposit::{Deposit, DEPOSIT_VERIFY_PER_GAS};
+pub use transfer::{Transfer, TRANSFER_VERIFY_PER_GAS};
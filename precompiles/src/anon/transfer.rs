@@ -0,0 +1,216 @@
+use ethabi::{ParamType, Token};
+use hypr_algebra::{bn254::BN254Scalar, serialization::FromToBytes};
+use hypr_api::{
+    anon_xfr::abar_to_abar::{verify_abar_to_abar_note, AbarToAbarNote},
+    parameters::VerifierParams,
+    structs::{AnonAssetRecord, AxfrOwnerMemo, Nullifier},
+};
+use lazy_static::lazy_static;
+use rayon::prelude::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+use sha3::{Digest, Sha3_512};
+
+use crate::{utils, Error, Result};
+
+pub struct Transfer {
+    nullifiers: Vec<[u8; 32]>,
+    root: [u8; 32],
+    commitments: Vec<[u8; 32]>,
+    proofs: Vec<Vec<u8>>,
+    memos: Vec<Vec<u8>>,
+}
+
+lazy_static! {
+    static ref PARAMS: VerifierParams = VerifierParams::get_abar_to_abar(1, 1).unwrap();
+}
+
+impl Transfer {
+    // abi "bytes32[]", "bytes32", "bytes32[]", "bytes[]", "bytes[]"
+    fn params_type() -> [ParamType; 5] {
+        let nullifiers = ParamType::Array(Box::new(ParamType::FixedBytes(32)));
+        let root = ParamType::FixedBytes(32);
+        let commitments = ParamType::Array(Box::new(ParamType::FixedBytes(32)));
+        let proofs = ParamType::Array(Box::new(ParamType::Bytes));
+        let memos = ParamType::Array(Box::new(ParamType::Bytes));
+        [nullifiers, root, commitments, proofs, memos]
+    }
+
+    fn require(&self) -> Result<()> {
+        let len = self.nullifiers.len();
+
+        if len == self.commitments.len()
+            && len == self.proofs.len()
+            && len == self.memos.len()
+        {
+            Ok(())
+        } else {
+            Err(Error::WrongLengthOfArguments)
+        }
+    }
+
+    pub fn new(data: &[u8]) -> Result<Self> {
+        let res = ethabi::decode(&Self::params_type(), data).map_err(|_| Error::ParseDataFailed)?;
+
+        let nullifiers = utils::into_bytes32_array(res.get(0).cloned())?;
+        let root = into_bytes32(res.get(1).cloned())?;
+        let commitments = utils::into_bytes32_array(res.get(2).cloned())?;
+        let proofs = utils::into_bytes_array(res.get(3).cloned())?;
+        let memos = utils::into_bytes_array(res.get(4).cloned())?;
+
+        let r = Self {
+            nullifiers,
+            root,
+            commitments,
+            proofs,
+            memos,
+        };
+
+        r.require()?;
+
+        Ok(r)
+    }
+
+    pub fn check(self) -> Result<()> {
+        let res: Vec<_> = self
+            .nullifiers
+            .into_par_iter()
+            .zip(self.commitments)
+            .zip(self.proofs)
+            .zip(self.memos)
+            .map(|(((nullifier, commitment), proof), memo)| {
+                verify_atoa(&PARAMS, nullifier, self.root, &commitment, &proof, &memo)
+            })
+            .collect();
+
+        for r in res {
+            r?
+        }
+        Ok(())
+    }
+
+    pub fn gas(self) -> u64 {
+        TRANSFER_VERIFY_PER_GAS * self.proofs.len() as u64
+    }
+}
+
+pub const TRANSFER_VERIFY_PER_GAS: u64 = 50000;
+
+fn into_bytes32(token: Option<Token>) -> Result<[u8; 32]> {
+    let bytes = token
+        .and_then(Token::into_fixed_bytes)
+        .ok_or(Error::ParseDataFailed)?;
+    bytes.try_into().map_err(|_| Error::ParseDataFailed)
+}
+
+fn verify_atoa(
+    params: &VerifierParams,
+    nullifier: [u8; 32],
+    root: [u8; 32],
+    commitment: &[u8; 32],
+    proof: &[u8],
+    memo: &[u8],
+) -> Result<()> {
+    // The Merkle root the spend proof was generated against. Binding it here is
+    // what stops a proof produced for one tree state from being replayed against
+    // another: the same root is fed into the verifier below.
+    let root = BN254Scalar::from_bytes(&root).map_err(|_| Error::ParseDataFailed)?;
+    let nullifier =
+        Nullifier::from_bytes(&nullifier).map_err(|_| Error::ParseDataFailed)?;
+
+    let output = AnonAssetRecord {
+        commitment: BN254Scalar::from_bytes(commitment).map_err(|_| Error::ParseDataFailed)?,
+    };
+    let proof = bincode::deserialize(proof).map_err(|_| Error::ProofDecodeFailed)?;
+
+    let note = AbarToAbarNote {
+        inputs: vec![nullifier],
+        merkle_root: root,
+        outputs: vec![output],
+        proof,
+        owner_memos: vec![AxfrOwnerMemo::from_bytes(memo)],
+    };
+
+    let mut hasher = Sha3_512::new();
+    hasher.update(note.merkle_root.to_bytes());
+    // Commit the owner memo ciphertext into the transcript in step with the
+    // prover, matching the deposit direction: a u64 big-endian length prefix
+    // keeps the memo bytes unambiguous when folded in after the root.
+    hasher.update((memo.len() as u64).to_be_bytes());
+    hasher.update(memo);
+
+    verify_abar_to_abar_note(params, &note, &note.merkle_root, hasher)
+        .map_err(|_| Error::ProofVerificationFailed)
+}
+
+#[cfg(test)]
+mod test {
+    use ethabi::Token;
+
+    use super::Transfer;
+    use crate::Error;
+
+    fn encode(
+        nullifiers: &[[u8; 32]],
+        root: [u8; 32],
+        commitments: &[[u8; 32]],
+        proofs: &[Vec<u8>],
+        memos: &[Vec<u8>],
+    ) -> Vec<u8> {
+        ethabi::encode(&[
+            Token::Array(nullifiers.iter().map(|n| Token::FixedBytes(n.to_vec())).collect()),
+            Token::FixedBytes(root.to_vec()),
+            Token::Array(commitments.iter().map(|c| Token::FixedBytes(c.to_vec())).collect()),
+            Token::Array(proofs.iter().map(|p| Token::Bytes(p.clone())).collect()),
+            Token::Array(memos.iter().map(|m| Token::Bytes(m.clone())).collect()),
+        ])
+    }
+
+    #[test]
+    fn test_require_and_gas() {
+        let data = encode(
+            &[[0x11; 32], [0x22; 32]],
+            [0x33; 32],
+            &[[0x44; 32], [0x55; 32]],
+            &[vec![0xab], vec![0xcd]],
+            &[vec![], vec![]],
+        );
+        let transfer = Transfer::new(&data).expect("well-formed batch");
+        assert_eq!(100000, transfer.gas());
+    }
+
+    #[test]
+    fn test_wrong_length_rejected() {
+        // One nullifier but two commitments: the per-note arrays don't line up.
+        let data = encode(
+            &[[0x11; 32]],
+            [0x33; 32],
+            &[[0x44; 32], [0x55; 32]],
+            &[vec![0xab]],
+            &[vec![]],
+        );
+        assert!(matches!(
+            Transfer::new(&data),
+            Err(Error::WrongLengthOfArguments)
+        ));
+    }
+
+    #[test]
+    fn test_root_is_anchored() {
+        // The anchor root is the first thing `verify_atoa` parses and it is
+        // handed straight to the verifier, so a root that is not a canonical
+        // field element is rejected (`ParseDataFailed`) before any proof work.
+        // This only covers the parse step: the binding itself comes from the
+        // parsed root being hashed into the transcript and passed to the
+        // verifier, which needs a real proof to exercise and is not checked
+        // here. Nullifier/commitment are canonical scalars here so
+        // the failure is unambiguously attributable to the root.
+        let scalar = hex::decode("2e5cee2ca3c56caf722797738332415647acb7cdc28db468c20f40f422c53927")
+            .unwrap();
+        let scalar: [u8; 32] = scalar.try_into().unwrap();
+
+        let bad_root = encode(&[scalar], [0xff; 32], &[scalar], &[vec![0x00]], &[vec![]]);
+        assert!(matches!(
+            Transfer::new(&bad_root).unwrap().check(),
+            Err(Error::ParseDataFailed)
+        ));
+    }
+}
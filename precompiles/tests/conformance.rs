@@ -0,0 +1,101 @@
+//! Conformance driver for the deposit/transfer verification precompiles.
+//!
+//! Test vectors live as JSON files under `tests/vectors/`, modeled on the
+//! layout of Ethereum's GeneralStateTests: each file is an array of cases so
+//! that external proof-generator implementations can contribute cross-checks by
+//! dropping a file in, without touching any Rust. Each case carries the
+//! ABI-encoded calldata, a `kind` selecting the precompile under test, and the
+//! expected outcome (`"ok"` or an `Error` variant name) plus the expected gas.
+
+use precompiles::{
+    anon::{deposit::Deposit, transfer::Transfer},
+    Error,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Vector {
+    name: String,
+    /// Which precompile the calldata targets; defaults to `deposit`.
+    #[serde(default = "default_kind")]
+    kind: String,
+    /// Hex of the ABI-encoded calldata handed to the precompile.
+    abi: String,
+    /// Either `"ok"` or the name of the expected `Error` variant.
+    expect: String,
+    /// Gas the precompile must charge once the calldata parses.
+    expected_gas: u64,
+}
+
+fn default_kind() -> String {
+    "deposit".to_string()
+}
+
+/// Canonical name for an error, matching the `expect` strings used in vectors.
+fn code(err: &Error) -> &'static str {
+    match err {
+        Error::WrongLengthOfArguments => "WrongLengthOfArguments",
+        Error::ParseDataFailed => "ParseDataFailed",
+        Error::ProofDecodeFailed => "ProofDecodeFailed",
+        Error::ProofVerificationFailed => "ProofVerificationFailed",
+        _ => "Unknown",
+    }
+}
+
+/// Assert that a `(new -> gas -> check)` pipeline matches the vector. Both
+/// `gas` and `check` consume the value, so the caller hands us constructors for
+/// fresh instances.
+fn assert_pipeline<T>(
+    v: &Vector,
+    new: impl Fn() -> Result<T, Error>,
+    gas: impl Fn(T) -> u64,
+    check: impl Fn(T) -> Result<(), Error>,
+) {
+    match new() {
+        // Calldata that fails to parse never reaches gas accounting.
+        Err(err) => assert_eq!(code(&err), v.expect, "{}: new() error mismatch", v.name),
+        Ok(parsed) => {
+            assert_eq!(gas(parsed), v.expected_gas, "{}: gas mismatch", v.name);
+
+            let parsed = new().expect("re-decode");
+            match check(parsed) {
+                Ok(()) => assert_eq!("ok", v.expect, "{}: expected failure", v.name),
+                Err(err) => assert_eq!(code(&err), v.expect, "{}: check() error mismatch", v.name),
+            }
+        }
+    }
+}
+
+fn run_vector(v: &Vector) {
+    let data = hex::decode(&v.abi).unwrap_or_else(|e| panic!("{}: bad hex: {e}", v.name));
+
+    match v.kind.as_str() {
+        "deposit" => assert_pipeline(v, || Deposit::new(&data), Deposit::gas, Deposit::check),
+        "transfer" => assert_pipeline(v, || Transfer::new(&data), Transfer::gas, Transfer::check),
+        other => panic!("{}: unknown vector kind {other}", v.name),
+    }
+}
+
+#[test]
+fn conformance_vectors() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors");
+
+    let mut ran = 0usize;
+    for entry in std::fs::read_dir(dir).expect("read tests/vectors") {
+        let path = entry.expect("dir entry").path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+
+        let raw = std::fs::read_to_string(&path).expect("read vector file");
+        let vectors: Vec<Vector> = serde_json::from_str(&raw)
+            .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+
+        for v in &vectors {
+            run_vector(v);
+        }
+        ran += vectors.len();
+    }
+
+    assert!(ran > 0, "no conformance vectors were found");
+}